@@ -4,99 +4,428 @@ use bevy::prelude::*;
 use bevy::reflect::TypeUuid;
 use bevy_inspector_egui::Inspectable;
 
+/// Selects the shaping function applied to each octave's noise value before
+/// it is accumulated, mirroring the planet-demo noise settings.
+#[derive(Inspectable, Clone, Copy, PartialEq)]
+pub enum NoiseType {
+    FBm,
+    Ridged,
+    Billow,
+}
+
+impl Default for NoiseType {
+    fn default() -> Self {
+        NoiseType::FBm
+    }
+}
+
 /// Stores all parameters for the noise map generation.
 /// It is adjustable via the inspector.
-#[derive(Inspectable, TypeUuid)]
+#[derive(Inspectable, TypeUuid, Clone)]
 #[uuid = "243f32e0-f3ad-11eb-9a03-0242ac130003"]
 pub struct NoiseData {
     pub seed: u64,
+    pub noise_type: NoiseType,
     #[inspectable(min = 0.0, max = 100.0)]
     pub scale: f64,
     #[inspectable(min = 1, max = 6)]
     pub octaves: u32,
     #[inspectable(min = 0.0, max = 1.0, speed = 0.01)]
     pub persistence: f32,
+    pub offset: Vec2,
+    #[inspectable(min = 0.0, max = 10.0, speed = 0.01)]
+    pub base_roughness: f64,
     #[inspectable(min = 1.0, max = 10.0, speed = 0.01)]
-    pub lacunarity: f64,
+    pub roughness: f64,
+    #[inspectable(min = 0.0, max = 1.0, speed = 0.01)]
+    pub min_value: f32,
 }
 
 impl Default for NoiseData {
     fn default() -> Self {
         Self {
             seed: 0,
+            noise_type: NoiseType::FBm,
             scale: 40.0,
             octaves: 4,
             persistence: 0.5,
-            lacunarity: 3.0,
+            offset: Vec2::ZERO,
+            base_roughness: 1.0,
+            roughness: 2.0,
+            min_value: 0.0,
+        }
+    }
+}
+
+impl NoiseData {
+    /// Shapes a single octave's raw noise sample (in `[-1, 1]`) according to
+    /// `noise_type`. `FBm` is passed through unchanged and stays in
+    /// `[-1, 1]`; `Ridged` folds the value around zero and squares it to
+    /// carve sharp mountain ridges, landing in `[0, 1]`; `Billow` folds it
+    /// the other way to produce rolling, billowy hills, staying in
+    /// `[-1, 1]`.
+    pub fn shape_octave(&self, n: f32) -> f32 {
+        match self.noise_type {
+            NoiseType::FBm => n,
+            NoiseType::Ridged => {
+                let v = 1.0 - n.abs();
+                v * v
+            }
+            NoiseType::Billow => 2.0 * n.abs() - 1.0,
+        }
+    }
+
+    /// Returns the sampling frequency for the given octave (0-indexed).
+    /// The first octave uses `base_roughness`; each subsequent octave
+    /// multiplies the previous frequency by `roughness`.
+    pub fn octave_frequency(&self, octave: u32) -> f64 {
+        self.base_roughness * self.roughness.powi(octave as i32)
+    }
+
+    /// Normalizes the amplitude-weighted sum of shaped octaves into a
+    /// `[0, 1]` height. `Ridged` octaves are already confined to `[0, 1]`
+    /// by `shape_octave`, so their weighted average is used as-is; `FBm`
+    /// and `Billow` octaves land in `[-1, 1]` and need remapping from
+    /// there. Mixing the two remaps would push every `Ridged` vertex into
+    /// the upper half of the height range, making basins/water unreachable.
+    pub fn normalize(&self, sum: f32, max_amplitude: f32) -> f32 {
+        if max_amplitude <= 0.0 {
+            return 0.0;
+        }
+        let average = sum / max_amplitude;
+        match self.noise_type {
+            NoiseType::Ridged => average,
+            NoiseType::FBm | NoiseType::Billow => (average + 1.0) / 2.0,
+        }
+    }
+
+    /// Flattens anything below `min_value` to zero, carving flat basins
+    /// out of the summed noise.
+    pub fn clamp_min(&self, value: f32) -> f32 {
+        if value < self.min_value {
+            0.0
+        } else {
+            value
         }
     }
 }
 
-/// Stores the parameters for the height adjustment of the map.
+/// Width of the bank transition, as a fraction of `river_size`, over which
+/// `RiverData::carve`'s underwater clamp fades out. Kept narrow so the
+/// channel floor is reliably underwater across most of its width, not just
+/// at the centerline.
+const RIVER_CLAMP_BAND: f32 = 0.2;
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + t * (b - a)
+}
+
+/// Stores the parameters for the river-carving pass, sampled from its own
+/// Perlin field independent of `NoiseData`.
+#[derive(Inspectable, TypeUuid, Clone)]
+#[uuid = "0b9a5c52-0e3b-4b7f-9a1f-6d9f6c8f6a1a"]
+pub struct RiverData {
+    pub seed: u64,
+    #[inspectable(min = 0.0, max = 1.0, speed = 0.01)]
+    pub river_size: f32,
+    #[inspectable(min = 0.0, max = 1.0, speed = 0.01)]
+    pub river_depth: f32,
+}
+
+impl Default for RiverData {
+    fn default() -> Self {
+        Self {
+            seed: 1,
+            river_size: 0.05,
+            river_depth: 0.2,
+        }
+    }
+}
+
+impl RiverData {
+    /// Carves a river channel into `height` using a river-noise sample `r`
+    /// (from an independent Perlin field). Vertices outside the channel
+    /// (`|r| >= river_size`) are returned untouched; vertices inside it are
+    /// lowered toward a river bed. The bed is forced at or below
+    /// `water_level` for most of the channel width, fading that clamp out
+    /// only across the last `RIVER_CLAMP_BAND` fraction near the bank
+    /// (`t -> 1`) so there is no discontinuity where the channel meets the
+    /// untouched `height`. Based on the Valleys mapgen technique used by
+    /// Minetest.
+    pub fn carve(&self, height: f32, r: f32, water_level: f32) -> f32 {
+        let d = r.abs();
+        if d >= self.river_size || self.river_size <= 0.0 {
+            return height;
+        }
+        let t = d / self.river_size;
+        // At t == 1.0 this equals `height` exactly, matching the untouched
+        // value just outside the channel.
+        let carved = height - self.river_depth * (1.0 - t * t);
+
+        let transition_start = 1.0 - RIVER_CLAMP_BAND;
+        let clamp_strength = if t <= transition_start {
+            1.0
+        } else {
+            (1.0 - t) / RIVER_CLAMP_BAND
+        };
+        lerp(carved, carved.min(water_level), clamp_strength)
+    }
+}
+
+/// Outputs at or below this are treated as part of the flat water shelf by
+/// `HeightCurve::water_level`. A small epsilon rather than an exact `0.0`
+/// comparison, since control points are user-editable floats.
+const WATER_SHELF_EPSILON: f32 = 1e-4;
+
+/// Stores the height remap curve as user-editable control points
+/// (input -> output, sorted ascending by `x`), evaluated with monotone
+/// cubic Hermite interpolation so the remap never overshoots between
+/// points and flat shelves (like the water level) stay perfectly flat.
 /// It is adjustable via the inspector.
-#[derive(Inspectable, TypeUuid)]
+#[derive(Inspectable, TypeUuid, Clone)]
 #[uuid = "abe9653e-ff3e-11eb-9a03-0242ac130003"]
 pub struct HeightCurve {
-    #[inspectable(min = 0.0, max = 1.0, speed = 0.01)]
-    pub water_level: f32,
-    #[inspectable(min = 1.0, max = 5.0, speed = 0.01)]
-    pub slope: f32,
+    pub points: Vec<Vec2>,
 }
 
 impl Default for HeightCurve {
     fn default() -> Self {
+        // Reproduces the old water-shelf-plus-slope shape
+        // (`water_level = 0.25`, `slope = 1.5`) as control points, so
+        // existing maps look unchanged.
         Self {
-            water_level: 0.25,
-            slope: 1.5,
+            points: vec![
+                Vec2::new(0.0, 0.0),
+                Vec2::new(0.25, 0.0),
+                Vec2::new(0.4, 0.0894),
+                Vec2::new(0.6, 0.3188),
+                Vec2::new(0.8, 0.6279),
+                Vec2::new(1.0, 1.0),
+            ],
         }
     }
 }
 
 impl HeightCurve {
-    /// Adjusts height values to flatten out the water and lower layers.
+    /// Adjusts height values using monotone cubic Hermite interpolation
+    /// between `points`.
     pub fn evaluate(&self, input: f32) -> f32 {
-        if input < self.water_level {
-            0.0
-        } else {
-            f32::powf(
-                (input - self.water_level) / (1.0 - self.water_level),
-                self.slope,
-            )
+        if self.points.len() < 2 {
+            return self.points.first().map_or(0.0, |p| p.y);
+        }
+
+        let min_x = self.points[0].x;
+        let max_x = self.points[self.points.len() - 1].x;
+        let input = input.clamp(min_x, max_x);
+
+        let tangents = self.tangents();
+        let k = self.segment_start(input);
+        let p0 = self.points[k];
+        let p1 = self.points[k + 1];
+        let dx = (p1.x - p0.x).max(f32::EPSILON);
+        let t = (input - p0.x) / dx;
+
+        let m0 = tangents[k] * dx;
+        let m1 = tangents[k + 1] * dx;
+
+        let t2 = t * t;
+        let t3 = t2 * t;
+        let h00 = 2.0 * t3 - 3.0 * t2 + 1.0;
+        let h10 = t3 - 2.0 * t2 + t;
+        let h01 = -2.0 * t3 + 3.0 * t2;
+        let h11 = t3 - t2;
+
+        h00 * p0.y + h10 * m0 + h01 * p1.y + h11 * m1
+    }
+
+    /// Returns the input value where the curve's flat water shelf ends —
+    /// the control point just before the first one whose output rises
+    /// above `WATER_SHELF_EPSILON`. Used in place of a fixed `water_level`
+    /// field now that the curve is arbitrary control points.
+    pub fn water_level(&self) -> f32 {
+        self.points
+            .iter()
+            .position(|p| p.y > WATER_SHELF_EPSILON)
+            .map_or(0.0, |i| self.points[i.saturating_sub(1)].x)
+    }
+
+    /// Finds the index `k` of the segment such that
+    /// `points[k].x <= input <= points[k + 1].x`. Falls back to the last
+    /// segment instead of panicking if `points` isn't sorted ascending by
+    /// `x` (e.g. mid-edit in the inspector).
+    fn segment_start(&self, input: f32) -> usize {
+        let last = self.points.len().saturating_sub(2);
+        self.points
+            .windows(2)
+            .position(|w| input >= w[0].x && input <= w[1].x)
+            .unwrap_or(last)
+    }
+
+    /// Computes a monotonicity-preserving tangent for each control point
+    /// via the Fritsch-Carlson method: start from the average of the
+    /// adjacent secant slopes, then rescale each segment's pair of tangents
+    /// so their ratios to the segment's secant slope stay within a circle
+    /// of radius 3.
+    /// A segment with a zero secant (a flat shelf) forces both of its
+    /// tangents to zero so the shelf stays exactly flat.
+    fn tangents(&self) -> Vec<f32> {
+        let n = self.points.len();
+        let mut secants = vec![0.0; n - 1];
+        for k in 0..n - 1 {
+            let dx = (self.points[k + 1].x - self.points[k].x).max(f32::EPSILON);
+            secants[k] = (self.points[k + 1].y - self.points[k].y) / dx;
+        }
+
+        let mut tangents = vec![0.0; n];
+        tangents[0] = secants[0];
+        tangents[n - 1] = secants[n - 2];
+        for k in 1..n - 1 {
+            tangents[k] = (secants[k - 1] + secants[k]) / 2.0;
+        }
+
+        for k in 0..n - 1 {
+            if secants[k] == 0.0 {
+                tangents[k] = 0.0;
+                tangents[k + 1] = 0.0;
+                continue;
+            }
+            let alpha = tangents[k] / secants[k];
+            let beta = tangents[k + 1] / secants[k];
+            let magnitude = (alpha * alpha + beta * beta).sqrt();
+            if magnitude > 3.0 {
+                let tau = 3.0 / magnitude;
+                tangents[k] = tau * alpha * secants[k];
+                tangents[k + 1] = tau * beta * secants[k];
+            }
         }
+
+        tangents
     }
 }
 
+/// A single entry of the biome palette: a point in (temperature, humidity)
+/// space and the color a vertex near that point is tinted.
+#[derive(Inspectable, Clone)]
+pub struct Biome {
+    #[inspectable(min = 0.0, max = 1.0, speed = 0.01)]
+    pub temperature: f32,
+    #[inspectable(min = 0.0, max = 1.0, speed = 0.01)]
+    pub humidity: f32,
+    pub color: Color,
+}
+
 /// Stores the parameters for the map material.
 /// It is adjustable via the inspector.
-#[derive(Inspectable, TypeUuid)]
+#[derive(Inspectable, TypeUuid, Clone)]
 #[uuid = "5de92f89-23f6-405e-8380-2ff1f1cec95b"]
 pub struct MaterialData {
-    pub layer_colors: Vec<Color>,
-    #[inspectable(min = 0.0, max = 1.0, speed = 0.01)]
-    pub layer_heights: Vec<f32>,
+    pub biomes: Vec<Biome>,
     #[inspectable(min = 0.0, max = 1.0, speed = 0.01)]
     pub blend_values: Vec<f32>,
+    #[inspectable(min = 0.0, max = 1.0, speed = 0.01)]
+    pub base_temp: f32,
+    #[inspectable(min = 0.0, max = 1.0, speed = 0.01)]
+    pub base_humidity: f32,
+    #[inspectable(min = 0.0, max = 5.0, speed = 0.01)]
+    pub altitude_chill: f32,
 }
 
 impl Default for MaterialData {
     fn default() -> Self {
         Self {
-            layer_colors: vec![
-                Color::BLUE,
-                Color::GREEN,
-                Color::DARK_GREEN,
-                Color::GRAY,
-                Color::WHITE,
+            biomes: vec![
+                Biome {
+                    temperature: 0.0,
+                    humidity: 0.5,
+                    color: Color::WHITE,
+                },
+                Biome {
+                    temperature: 0.8,
+                    humidity: 0.1,
+                    color: Color::GRAY,
+                },
+                Biome {
+                    temperature: 0.4,
+                    humidity: 0.3,
+                    color: Color::DARK_GREEN,
+                },
+                Biome {
+                    temperature: 0.5,
+                    humidity: 0.7,
+                    color: Color::GREEN,
+                },
+                Biome {
+                    temperature: 0.3,
+                    humidity: 0.9,
+                    color: Color::BLUE,
+                },
             ],
-            layer_heights: vec![0.2, 0.35, 0.5, 0.8],
-            blend_values: vec![0.05, 0.05, 0.1, 0.15],
+            blend_values: vec![0.05, 0.05, 0.1, 0.15, 0.05],
+            base_temp: 0.7,
+            base_humidity: 0.4,
+            altitude_chill: 0.6,
+        }
+    }
+}
+
+impl MaterialData {
+    /// Temperature at a vertex, cooling with height above the water level.
+    pub fn temperature(&self, height_above_water: f32) -> f32 {
+        (self.base_temp - height_above_water.max(0.0) * self.altitude_chill).clamp(0.0, 1.0)
+    }
+
+    /// Humidity at a vertex, boosted near low/water areas and near rivers.
+    pub fn humidity(&self, height_above_water: f32, near_river: bool) -> f32 {
+        let mut humidity = self.base_humidity + (1.0 - height_above_water.max(0.0)).max(0.0) * 0.3;
+        if near_river {
+            humidity += 0.2;
+        }
+        humidity.clamp(0.0, 1.0)
+    }
+
+    /// Blends the colors of the nearest biomes for the given (temperature,
+    /// humidity) pair (Whittaker-style biome coloring). The nearest biome
+    /// gets full weight; another biome is blended in only while its
+    /// distance is within its entry in `blend_values` of the nearest one,
+    /// fading linearly to zero at that margin.
+    pub fn biome_color(&self, temp: f32, humidity: f32) -> Color {
+        let distances: Vec<f32> = self
+            .biomes
+            .iter()
+            .zip(self.blend_values.iter())
+            .map(|(biome, _)| {
+                let dt = temp - biome.temperature;
+                let dh = humidity - biome.humidity;
+                (dt * dt + dh * dh).sqrt()
+            })
+            .collect();
+        let nearest = distances.iter().copied().fold(f32::INFINITY, f32::min);
+
+        let mut total_weight = 0.0;
+        let mut blended = [0.0f32; 4];
+        for ((biome, blend), distance) in self.biomes.iter().zip(self.blend_values.iter()).zip(distances) {
+            let weight = (1.0 - (distance - nearest) / blend.max(f32::EPSILON)).clamp(0.0, 1.0);
+            total_weight += weight;
+            for (channel, value) in blended.iter_mut().zip(biome.color.as_rgba_f32()) {
+                *channel += value * weight;
+            }
+        }
+        if total_weight > 0.0 {
+            Color::rgba(
+                blended[0] / total_weight,
+                blended[1] / total_weight,
+                blended[2] / total_weight,
+                blended[3] / total_weight,
+            )
+        } else {
+            Color::WHITE
         }
     }
 }
 
 /// Stores all parameters of a map.
 /// It is adjustable via the inspector.
-#[derive(Inspectable, TypeUuid)]
+#[derive(Inspectable, TypeUuid, Clone)]
 #[uuid = "fd016f46-f3a6-11eb-9a03-0242ac130003"]
 pub struct MapData {
     pub wireframe: bool,
@@ -107,6 +436,8 @@ pub struct MapData {
     #[inspectable(collapse)]
     pub noise_data: NoiseData,
     #[inspectable(collapse)]
+    pub river_data: RiverData,
+    #[inspectable(collapse)]
     pub height_curve: HeightCurve,
     #[inspectable(collapse)]
     pub material_data: MaterialData,
@@ -119,6 +450,7 @@ impl Default for MapData {
             map_height: 10.0,
             level_of_detail: 0,
             noise_data: Default::default(),
+            river_data: Default::default(),
             height_curve: Default::default(),
             material_data: Default::default(),
         }
@@ -134,4 +466,173 @@ impl MapData {
     pub fn generate(&self) -> (Mesh, MapMaterial) {
         (MapShape::new(self).into(), MapMaterial::new(self))
     }
+
+    /// Generates the mesh for a single terrain chunk at `coord` (in
+    /// chunk-grid coordinates), `chunk_size` world units wide, overriding
+    /// `level_of_detail` and applying border skirts of `skirt_depth` to
+    /// hide seams against lower-detail neighbors. Noise sample coordinates
+    /// are offset by `coord * chunk_size` so chunks sharing the same seed
+    /// tile continuously at their edges.
+    pub fn generate_chunk(
+        &self,
+        coord: IVec2,
+        chunk_size: f32,
+        level_of_detail: usize,
+        skirt_depth: f32,
+    ) -> (Mesh, MapMaterial) {
+        let mut chunk_data = self.clone();
+        chunk_data.level_of_detail = level_of_detail;
+        (
+            MapShape::new_chunk(&chunk_data, chunk_size, coord, skirt_depth).into(),
+            MapMaterial::new(&chunk_data),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shape_octave_ranges_match_noise_type() {
+        let mut noise_data = NoiseData::default();
+        for n in [-1.0, -0.5, 0.0, 0.5, 1.0] {
+            noise_data.noise_type = NoiseType::FBm;
+            assert_eq!(noise_data.shape_octave(n), n);
+
+            noise_data.noise_type = NoiseType::Ridged;
+            let ridged = noise_data.shape_octave(n);
+            assert!((0.0..=1.0).contains(&ridged), "ridged({n}) = {ridged}");
+
+            noise_data.noise_type = NoiseType::Billow;
+            let billow = noise_data.shape_octave(n);
+            assert!((-1.0..=1.0).contains(&billow), "billow({n}) = {billow}");
+        }
+    }
+
+    #[test]
+    fn normalize_keeps_ridged_in_full_unit_range() {
+        let mut noise_data = NoiseData::default();
+        noise_data.noise_type = NoiseType::Ridged;
+        // A weighted average of shape_octave's [0, 1] outputs should be
+        // left alone, not squeezed into [0.5, 1.0] by the FBm/Billow remap.
+        assert_eq!(noise_data.normalize(0.0, 1.0), 0.0);
+        assert_eq!(noise_data.normalize(1.0, 1.0), 1.0);
+        assert_eq!(noise_data.normalize(0.5, 1.0), 0.5);
+    }
+
+    #[test]
+    fn normalize_remaps_fbm_and_billow_from_plus_minus_one() {
+        let noise_data = NoiseData::default(); // FBm
+        assert_eq!(noise_data.normalize(-1.0, 1.0), 0.0);
+        assert_eq!(noise_data.normalize(1.0, 1.0), 1.0);
+        assert_eq!(noise_data.normalize(0.0, 1.0), 0.5);
+    }
+
+    #[test]
+    fn carve_matches_untouched_height_at_the_bank() {
+        let river = RiverData::default();
+        let height = 5.0;
+        let carved = river.carve(height, river.river_size, 1.0);
+        assert!((carved - height).abs() < 1e-5);
+    }
+
+    #[test]
+    fn carve_is_continuous_across_the_bank() {
+        let river = RiverData::default();
+        let height = 5.0;
+        let water_level = 1.0;
+        let just_inside = river.carve(height, river.river_size * 0.9999, water_level);
+        let just_outside = river.carve(height, river.river_size * 1.0001, water_level);
+        assert!((just_inside - just_outside).abs() < 0.01);
+    }
+
+    #[test]
+    fn carve_forces_most_of_the_channel_underwater() {
+        let river = RiverData::default();
+        let water_level = 1.0;
+        // Well above water, away from the bank, the channel floor must
+        // still end up at or below water_level.
+        for t in [0.0, 0.2, 0.4, 0.6, 0.79] {
+            let r = river.river_size * t;
+            let carved = river.carve(10.0, r, water_level);
+            assert!(carved <= water_level + 1e-5, "t={t} carved={carved}");
+        }
+    }
+
+    #[test]
+    fn carve_leaves_terrain_outside_the_channel_untouched() {
+        let river = RiverData::default();
+        assert_eq!(river.carve(5.0, river.river_size * 2.0, 1.0), 5.0);
+    }
+
+    #[test]
+    fn temperature_cools_with_height_above_water() {
+        let material = MaterialData::default();
+        assert!(material.temperature(0.0) > material.temperature(0.5));
+    }
+
+    #[test]
+    fn humidity_is_boosted_near_rivers() {
+        let material = MaterialData::default();
+        assert!(material.humidity(0.5, true) > material.humidity(0.5, false));
+    }
+
+    #[test]
+    fn water_level_finds_end_of_flat_shelf() {
+        let curve = HeightCurve::default();
+        assert!((curve.water_level() - 0.25).abs() < 1e-5);
+    }
+
+    #[test]
+    fn evaluate_keeps_flat_shelf_flat() {
+        let curve = HeightCurve::default();
+        assert_eq!(curve.evaluate(0.0), 0.0);
+        assert_eq!(curve.evaluate(0.1), 0.0);
+        assert_eq!(curve.evaluate(0.25), 0.0);
+    }
+
+    #[test]
+    fn evaluate_is_monotonically_increasing() {
+        let curve = HeightCurve::default();
+        let mut previous = curve.evaluate(0.0);
+        for i in 1..=20 {
+            let x = i as f32 / 20.0;
+            let value = curve.evaluate(x);
+            assert!(value >= previous - 1e-5, "curve dipped at x={x}");
+            previous = value;
+        }
+    }
+
+    #[test]
+    fn evaluate_reaches_the_endpoints() {
+        let curve = HeightCurve::default();
+        assert!((curve.evaluate(0.0) - 0.0).abs() < 1e-5);
+        assert!((curve.evaluate(1.0) - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn evaluate_does_not_panic_on_unsorted_points() {
+        let curve = HeightCurve {
+            points: vec![
+                Vec2::new(0.5, 0.5),
+                Vec2::new(0.0, 0.0),
+                Vec2::new(1.0, 1.0),
+            ],
+        };
+        let _ = curve.evaluate(0.3);
+    }
+
+    #[test]
+    fn biome_color_matches_palette_entry_at_its_own_point() {
+        let material = MaterialData::default();
+        let snow = &material.biomes[0];
+        let color = material.biome_color(snow.temperature, snow.humidity);
+        let [r, g, b, a] = color.as_rgba_f32();
+        let [er, eg, eb, ea] = snow.color.as_rgba_f32();
+        assert!((r - er).abs() < 0.05);
+        assert!((g - eg).abs() < 0.05);
+        assert!((b - eb).abs() < 0.05);
+        assert!((a - ea).abs() < 0.05);
+    }
 }
\ No newline at end of file
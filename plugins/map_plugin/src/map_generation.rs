@@ -0,0 +1,223 @@
+use crate::map_data::{MapData, NoiseData};
+use bevy::prelude::*;
+use bevy::render::mesh::Indices;
+use bevy::render::render_resource::PrimitiveTopology;
+
+/// World-space size of a standalone (non-chunked) map's mesh.
+const DEFAULT_MAP_SIZE: f32 = 50.0;
+/// Base vertex resolution of a map before `level_of_detail` skips vertices.
+const GRID_RESOLUTION: usize = 64;
+
+/// A lightweight seeded gradient noise, standing in for a full Perlin
+/// implementation until the project takes on a noise crate dependency.
+struct Perlin {
+    permutation: [u8; 512],
+}
+
+impl Perlin {
+    fn new(seed: u64) -> Self {
+        let mut table: [u8; 256] = [0; 256];
+        for (i, slot) in table.iter_mut().enumerate() {
+            *slot = i as u8;
+        }
+
+        // A small xorshift RNG seeded from `seed`, used only to shuffle the
+        // permutation table deterministically.
+        let mut state = seed ^ 0x9E3779B97F4A7C15;
+        for i in (1..table.len()).rev() {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let j = (state as usize) % (i + 1);
+            table.swap(i, j);
+        }
+
+        let mut permutation = [0u8; 512];
+        for i in 0..512 {
+            permutation[i] = table[i % 256];
+        }
+        Self { permutation }
+    }
+
+    fn fade(t: f32) -> f32 {
+        t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+    }
+
+    fn grad(hash: u8, x: f32, y: f32) -> f32 {
+        match hash & 3 {
+            0 => x + y,
+            1 => -x + y,
+            2 => x - y,
+            _ => -x - y,
+        }
+    }
+
+    /// Samples 2D gradient noise at `(x, y)`, returning a value in
+    /// `[-1.0, 1.0]`.
+    fn sample(&self, x: f32, y: f32) -> f32 {
+        let xi = (x.floor() as i32 & 255) as usize;
+        let yi = (y.floor() as i32 & 255) as usize;
+        let xf = x - x.floor();
+        let yf = y - y.floor();
+
+        let u = Self::fade(xf);
+        let v = Self::fade(yf);
+
+        let perm = &self.permutation;
+        let aa = perm[perm[xi] as usize + yi];
+        let ab = perm[perm[xi] as usize + yi + 1];
+        let ba = perm[perm[xi + 1] as usize + yi];
+        let bb = perm[perm[xi + 1] as usize + yi + 1];
+
+        let x1 = lerp(Self::grad(aa, xf, yf), Self::grad(ba, xf - 1.0, yf), u);
+        let x2 = lerp(
+            Self::grad(ab, xf, yf - 1.0),
+            Self::grad(bb, xf - 1.0, yf - 1.0),
+            u,
+        );
+        lerp(x1, x2, v).clamp(-1.0, 1.0)
+    }
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + t * (b - a)
+}
+
+/// Sums the octaves of `noise_data` (shaped via `NoiseType` and offset by
+/// the domain-warp `offset`) into a normalized height in `[0, 1]`.
+fn sample_noise(perlin: &Perlin, noise_data: &NoiseData, world_x: f32, world_z: f32) -> f32 {
+    let sample_pos = Vec2::new(world_x, world_z) + noise_data.offset;
+    let scale = noise_data.scale.max(0.0001) as f32;
+
+    let mut amplitude = 1.0;
+    let mut max_amplitude = 0.0;
+    let mut sum = 0.0;
+
+    for octave in 0..noise_data.octaves {
+        let frequency = noise_data.octave_frequency(octave) as f32 / scale;
+        let raw = perlin.sample(sample_pos.x * frequency, sample_pos.y * frequency);
+        sum += noise_data.shape_octave(raw) * amplitude;
+        max_amplitude += amplitude;
+        amplitude *= noise_data.persistence;
+    }
+
+    noise_data.clamp_min(noise_data.normalize(sum, max_amplitude))
+}
+
+/// Builds the triangle mesh for a map from its `MapData`.
+pub struct MapShape {
+    mesh: Mesh,
+}
+
+impl MapShape {
+    /// Builds the mesh for a standalone (non-chunked) map.
+    pub fn new(map_data: &MapData) -> Self {
+        Self::build(map_data, DEFAULT_MAP_SIZE, Vec2::ZERO, 0.0)
+    }
+
+    /// Builds the mesh for a single terrain chunk `chunk_size` world units
+    /// wide at `coord` (in chunk-grid coordinates), sampling noise from the
+    /// world-space origin `coord * chunk_size` so neighboring chunks
+    /// sharing the same seed tile continuously, and lowering border
+    /// vertices by `skirt_depth` to hide seams against lower-detail
+    /// neighbors.
+    pub fn new_chunk(map_data: &MapData, chunk_size: f32, coord: IVec2, skirt_depth: f32) -> Self {
+        let world_offset = Vec2::new(coord.x as f32, coord.y as f32) * chunk_size;
+        Self::build(map_data, chunk_size, world_offset, skirt_depth)
+    }
+
+    fn build(map_data: &MapData, size: f32, world_offset: Vec2, skirt_depth: f32) -> Self {
+        let noise_perlin = Perlin::new(map_data.noise_data.seed);
+        let river_perlin = Perlin::new(map_data.river_data.seed);
+        let water_level = map_data.height_curve.water_level() * map_data.map_height;
+
+        let skip = (map_data.level_of_detail * 2).max(1);
+        let vertices_per_line = (GRID_RESOLUTION / skip).max(2) + 1;
+        let step = size / (vertices_per_line - 1) as f32;
+
+        let mut positions = Vec::with_capacity(vertices_per_line * vertices_per_line);
+        let mut uvs = Vec::with_capacity(positions.capacity());
+        let mut colors = Vec::with_capacity(positions.capacity());
+        let mut indices = Vec::new();
+
+        for row in 0..vertices_per_line {
+            for col in 0..vertices_per_line {
+                let local_x = col as f32 * step;
+                let local_z = row as f32 * step;
+                let world_x = local_x + world_offset.x;
+                let world_z = local_z + world_offset.y;
+
+                let normalized = sample_noise(&noise_perlin, &map_data.noise_data, world_x, world_z);
+                let river_sample = river_perlin.sample(
+                    (world_x + map_data.noise_data.offset.x) / size,
+                    (world_z + map_data.noise_data.offset.y) / size,
+                );
+                let curved = map_data.height_curve.evaluate(normalized) * map_data.map_height;
+                let mut height = map_data.river_data.carve(curved, river_sample, water_level);
+
+                let near_river = river_sample.abs() < map_data.river_data.river_size;
+                let height_above_water = (height - water_level).max(0.0) / map_data.map_height.max(0.0001);
+                let temperature = map_data.material_data.temperature(height_above_water);
+                let humidity = map_data.material_data.humidity(height_above_water, near_river);
+                let color = map_data.material_data.biome_color(temperature, humidity);
+
+                let on_border =
+                    row == 0 || col == 0 || row == vertices_per_line - 1 || col == vertices_per_line - 1;
+                if on_border {
+                    height -= skirt_depth;
+                }
+
+                positions.push([local_x, height, local_z]);
+                uvs.push([
+                    col as f32 / (vertices_per_line - 1) as f32,
+                    row as f32 / (vertices_per_line - 1) as f32,
+                ]);
+                colors.push(color.as_rgba_f32());
+
+                if row < vertices_per_line - 1 && col < vertices_per_line - 1 {
+                    let top_left = (row * vertices_per_line + col) as u32;
+                    let top_right = top_left + 1;
+                    let bottom_left = top_left + vertices_per_line as u32;
+                    let bottom_right = bottom_left + 1;
+
+                    indices.extend_from_slice(&[top_left, bottom_left, top_right]);
+                    indices.extend_from_slice(&[top_right, bottom_left, bottom_right]);
+                }
+            }
+        }
+
+        let normals = compute_normals(&positions, &indices);
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+        mesh.set_indices(Some(Indices::U32(indices)));
+
+        Self { mesh }
+    }
+}
+
+fn compute_normals(positions: &[[f32; 3]], indices: &[u32]) -> Vec<[f32; 3]> {
+    let mut normals = vec![Vec3::ZERO; positions.len()];
+    for triangle in indices.chunks_exact(3) {
+        let a = Vec3::from(positions[triangle[0] as usize]);
+        let b = Vec3::from(positions[triangle[1] as usize]);
+        let c = Vec3::from(positions[triangle[2] as usize]);
+        let normal = (b - a).cross(c - a);
+        normals[triangle[0] as usize] += normal;
+        normals[triangle[1] as usize] += normal;
+        normals[triangle[2] as usize] += normal;
+    }
+    normals
+        .into_iter()
+        .map(|n| n.try_normalize().unwrap_or(Vec3::Y).into())
+        .collect()
+}
+
+impl From<MapShape> for Mesh {
+    fn from(shape: MapShape) -> Self {
+        shape.mesh
+    }
+}
@@ -0,0 +1,31 @@
+use crate::map_data::MapData;
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use bevy::render::render_resource::{AsBindGroup, ShaderRef};
+
+/// The material drawn for a map mesh. Biome coloring is baked into the
+/// mesh's per-vertex `ATTRIBUTE_COLOR` by `MapShape::build`, so this
+/// material only needs to track the wireframe toggle.
+#[derive(AsBindGroup, TypeUuid, Clone)]
+#[uuid = "c9b2a6d4-8f3a-4e9a-9b1a-6e3f2a7c4d10"]
+pub struct MapMaterial {
+    pub wireframe: bool,
+}
+
+impl MapMaterial {
+    pub fn new(map_data: &MapData) -> Self {
+        Self {
+            wireframe: map_data.wireframe,
+        }
+    }
+}
+
+impl Material for MapMaterial {
+    fn vertex_shader() -> ShaderRef {
+        "shaders/terrain.wgsl".into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        "shaders/terrain.wgsl".into()
+    }
+}
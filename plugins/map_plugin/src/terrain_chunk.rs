@@ -0,0 +1,184 @@
+use crate::map_data::MapData;
+use crate::map_pipeline::MapMaterial;
+use bevy::pbr::MaterialMeshBundle;
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use bevy_inspector_egui::Inspectable;
+use std::collections::HashSet;
+
+/// Marks the terrain chunk at `coord`, in chunk-grid coordinates (each
+/// chunk covering `ChunkStreamingData::chunk_size` world units).
+#[derive(Component)]
+pub struct TerrainChunk {
+    pub coord: IVec2,
+}
+
+/// Marks the camera that terrain chunks stream around, disambiguating it
+/// from any other camera in the scene (e.g. a UI camera).
+#[derive(Component)]
+pub struct StreamingCamera;
+
+/// Stores the parameters for chunked terrain streaming.
+/// It is adjustable via the inspector.
+#[derive(Inspectable, TypeUuid)]
+#[uuid = "6f2a9c3e-1b7a-4e3d-9e7a-4a5c3b2d1e90"]
+pub struct ChunkStreamingData {
+    #[inspectable(min = 10.0, max = 200.0)]
+    pub chunk_size: f32,
+    #[inspectable(min = 1, max = 16)]
+    pub view_distance: i32,
+    pub lod_distances: Vec<i32>,
+    #[inspectable(min = 0.0, max = 10.0, speed = 0.01)]
+    pub skirt_depth: f32,
+}
+
+impl Default for ChunkStreamingData {
+    fn default() -> Self {
+        Self {
+            chunk_size: 50.0,
+            view_distance: 4,
+            lod_distances: vec![1, 2, 3],
+            skirt_depth: 0.5,
+        }
+    }
+}
+
+impl ChunkStreamingData {
+    /// Converts a world-space position into the chunk-grid coordinate that
+    /// contains it.
+    pub fn world_to_chunk(&self, position: Vec3) -> IVec2 {
+        IVec2::new(
+            (position.x / self.chunk_size).floor() as i32,
+            (position.z / self.chunk_size).floor() as i32,
+        )
+    }
+
+    /// Returns the level of detail a chunk should use when it is
+    /// `distance` chunks away from the camera: one LOD step for each entry
+    /// in `lod_distances` the chunk has passed.
+    pub fn lod_for_distance(&self, distance: i32) -> usize {
+        self.lod_distances
+            .iter()
+            .filter(|&&threshold| distance >= threshold)
+            .count()
+    }
+}
+
+/// Spawns/despawns `TerrainChunk`s in a square of `view_distance` chunks
+/// around the `StreamingCamera`, generating each chunk's mesh so that
+/// neighboring chunks tile continuously, and assigns a level of detail
+/// based on distance from the camera.
+pub fn stream_chunks_around_camera(
+    mut commands: Commands,
+    map_data: Res<MapData>,
+    streaming: Res<ChunkStreamingData>,
+    camera_query: Query<&Transform, With<StreamingCamera>>,
+    chunk_query: Query<(Entity, &TerrainChunk)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<MapMaterial>>,
+) {
+    let Ok(camera_transform) = camera_query.get_single() else {
+        return;
+    };
+    let camera_chunk = streaming.world_to_chunk(camera_transform.translation);
+
+    let mut wanted = HashSet::new();
+    for dx in -streaming.view_distance..=streaming.view_distance {
+        for dz in -streaming.view_distance..=streaming.view_distance {
+            wanted.insert(camera_chunk + IVec2::new(dx, dz));
+        }
+    }
+
+    let existing: HashSet<IVec2> = chunk_query.iter().map(|(_, chunk)| chunk.coord).collect();
+
+    for (entity, chunk) in chunk_query.iter() {
+        if !wanted.contains(&chunk.coord) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    for coord in wanted.iter().filter(|coord| !existing.contains(coord)) {
+        let distance = (*coord - camera_chunk).abs().max_element();
+        let level_of_detail = streaming.lod_for_distance(distance);
+        let (mesh, material) = map_data.generate_chunk(
+            *coord,
+            streaming.chunk_size,
+            level_of_detail,
+            streaming.skirt_depth,
+        );
+
+        commands
+            .spawn(MaterialMeshBundle {
+                mesh: meshes.add(mesh),
+                material: materials.add(material),
+                transform: Transform::from_xyz(
+                    coord.x as f32 * streaming.chunk_size,
+                    0.0,
+                    coord.y as f32 * streaming.chunk_size,
+                ),
+                ..default()
+            })
+            .insert(TerrainChunk { coord: *coord });
+    }
+}
+
+/// Registers chunk streaming: the `ChunkStreamingData` resource and the
+/// system that keeps `TerrainChunk`s spawned around the `StreamingCamera`.
+pub struct ChunkStreamingPlugin;
+
+impl Plugin for ChunkStreamingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ChunkStreamingData>()
+            .add_system(stream_chunks_around_camera);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn world_to_chunk_floors_towards_the_chunk_containing_the_position() {
+        let streaming = ChunkStreamingData {
+            chunk_size: 50.0,
+            ..ChunkStreamingData::default()
+        };
+        assert_eq!(
+            streaming.world_to_chunk(Vec3::new(10.0, 0.0, 10.0)),
+            IVec2::new(0, 0)
+        );
+        assert_eq!(
+            streaming.world_to_chunk(Vec3::new(60.0, 0.0, -10.0)),
+            IVec2::new(1, -1)
+        );
+    }
+
+    #[test]
+    fn world_to_chunk_is_continuous_across_the_origin() {
+        let streaming = ChunkStreamingData {
+            chunk_size: 50.0,
+            ..ChunkStreamingData::default()
+        };
+        assert_eq!(
+            streaming.world_to_chunk(Vec3::new(-1.0, 0.0, 0.0)),
+            IVec2::new(-1, 0)
+        );
+        assert_eq!(
+            streaming.world_to_chunk(Vec3::new(0.0, 0.0, 0.0)),
+            IVec2::new(0, 0)
+        );
+    }
+
+    #[test]
+    fn lod_for_distance_steps_once_per_passed_threshold() {
+        let streaming = ChunkStreamingData {
+            lod_distances: vec![1, 2, 3],
+            ..ChunkStreamingData::default()
+        };
+        assert_eq!(streaming.lod_for_distance(0), 0);
+        assert_eq!(streaming.lod_for_distance(1), 1);
+        assert_eq!(streaming.lod_for_distance(2), 2);
+        assert_eq!(streaming.lod_for_distance(3), 3);
+        assert_eq!(streaming.lod_for_distance(10), 3);
+    }
+}